@@ -0,0 +1,157 @@
+//! Cascaded biquad sections, used to build filter responses of order higher than two out of a
+//! chain of `DirectForm2Transposed` stages.
+
+use num_complex::Complex;
+
+use crate::{Biquad, Coefficients, DirectForm2Transposed, Errors, Hertz, Sample, Type};
+
+/// A series of `N` `DirectForm2Transposed` biquads, run in series to realise a filter response of
+/// order up to `2 * N`.
+#[derive(Clone, Debug)]
+pub struct Cascade<T: Sample, const N: usize> {
+    sections: [DirectForm2Transposed<T>; N],
+    coefficients: [Coefficients<T>; N],
+}
+
+impl<T: Sample, const N: usize> Cascade<T, N> {
+    /// Creates a cascade directly from `N` already designed sets of coefficients
+    pub fn new(coefficients: [Coefficients<T>; N]) -> Self {
+        Cascade {
+            sections: coefficients.map(DirectForm2Transposed::new),
+            coefficients,
+        }
+    }
+
+    /// The complex frequency response of the whole cascade at `f`, the product of each
+    /// section's `Coefficients::frequency_response`
+    pub fn frequency_response(&self, fs: Hertz, f: Hertz) -> Complex<T> {
+        self.coefficients.iter().fold(Complex::new(T::one(), T::zero()), |acc, c| {
+            acc * c.frequency_response(fs, f)
+        })
+    }
+
+    /// The magnitude of the cascade's frequency response at `f`, in decibels
+    pub fn magnitude_db(&self, fs: Hertz, f: Hertz) -> T {
+        let h = self.frequency_response(fs, f);
+        let magnitude = (h.re * h.re + h.im * h.im).sqrt();
+        T::from(20.0).unwrap() * magnitude.log10()
+    }
+
+    /// Designs a maximally flat `order`-th order Butterworth `filter_type` cascade at cutoff
+    /// `f0` and sample rate `fs`. `N` must equal `order.div_ceil(2)`; for odd orders the first
+    /// section is a first order (single real pole) stage.
+    pub fn butterworth(
+        filter_type: Type<T>,
+        fs: Hertz,
+        f0: Hertz,
+        order: usize,
+    ) -> Result<Self, Errors> {
+        if order == 0 || order.div_ceil(2) != N {
+            return Err(Errors::OutsideNyquist);
+        }
+
+        let mut coefficients = [Coefficients {
+            a1: T::zero(),
+            a2: T::zero(),
+            b0: T::zero(),
+            b1: T::zero(),
+            b2: T::zero(),
+        }; N];
+
+        let odd_order = order % 2 == 1;
+        let first_order_offset = if odd_order { 1 } else { 0 };
+
+        if odd_order {
+            coefficients[0] = first_order_section(filter_type, fs, f0)?;
+        }
+
+        for (i, coeffs) in coefficients[first_order_offset..].iter_mut().enumerate() {
+            let q = if odd_order {
+                butterworth_q_odd(order, i + 1)
+            } else {
+                butterworth_q_even(order, i)
+            };
+            *coeffs = Coefficients::from_params(filter_type, fs, f0, q)?;
+        }
+
+        Ok(Self::new(coefficients))
+    }
+
+    /// Runs a single sample through every section in series
+    pub fn run(&mut self, input: T) -> T {
+        let mut out = input;
+        for section in self.sections.iter_mut() {
+            out = section.run(out);
+        }
+        out
+    }
+
+    /// Replaces all `N` sections' coefficients at once, keeping `sections` and `coefficients` (and
+    /// therefore `run` and `frequency_response`/`magnitude_db`) in agreement. There is no single
+    /// `Coefficients<T>` that is correct for every section of a cascade, so `Cascade` does not
+    /// implement `Biquad`.
+    pub fn set_coefficients(&mut self, new_coefficients: [Coefficients<T>; N]) {
+        for (section, coeffs) in self.sections.iter_mut().zip(new_coefficients.iter()) {
+            section.update_coefficients(*coeffs);
+        }
+        self.coefficients = new_coefficients;
+    }
+}
+
+/// The per-section Q value for pole pair `k` (0-indexed) of an even `order`-th order Butterworth
+/// filter: `Q_k = 1 / (2 * cos(pi * (2k + 1) / (2 * order)))`.
+fn butterworth_q_even<T: Sample>(order: usize, k: usize) -> T {
+    let two = T::from(2.0).unwrap();
+    let numerator = T::PI() * T::from(2 * k + 1).unwrap();
+    let denominator = two * T::from(order).unwrap();
+    T::one() / (two * (numerator / denominator).cos())
+}
+
+/// The per-section Q value for pole pair `k` (1-indexed, `k = 1..=(order - 1) / 2`) of the
+/// conjugate pole pairs remaining once the single real pole has been split off an odd
+/// `order`-th order Butterworth filter: `Q_k = 1 / (2 * cos(k * pi / order))`.
+fn butterworth_q_odd<T: Sample>(order: usize, k: usize) -> T {
+    let numerator = T::PI() * T::from(k).unwrap();
+    let denominator = T::from(order).unwrap();
+    T::one() / (T::from(2.0).unwrap() * (numerator / denominator).cos())
+}
+
+/// A first order (single real pole) section used to fill out odd-order Butterworth cascades, via
+/// the standard bilinear-transformed one-pole lowpass/highpass (`H(s) = omega_c / (s + omega_c)`
+/// or `H(s) = s / (s + omega_c)`). Derived directly rather than through `Coefficients::from_analog`
+/// so the resulting biquad's `a2`/`b2` are exactly zero instead of carrying a spurious pole/zero
+/// pair at `z = -1` from routing a degree-1 prototype through degree-2 machinery.
+fn first_order_section<T: Sample>(
+    filter_type: Type<T>,
+    fs: Hertz,
+    f0: Hertz,
+) -> Result<Coefficients<T>, Errors> {
+    if f0.hz() * 2.0 > fs.hz() {
+        return Err(Errors::OutsideNyquist);
+    }
+
+    let fs_t = T::from(fs.hz()).unwrap();
+    let f0_t = T::from(f0.hz()).unwrap();
+    let omega_c = T::from(2.0).unwrap() * fs_t * (T::PI() * f0_t / fs_t).tan();
+    let k = T::from(2.0).unwrap() * fs_t;
+    let a1 = (omega_c - k) / (k + omega_c);
+
+    let (b0, b1) = match filter_type {
+        Type::HighPass => {
+            let b0 = k / (k + omega_c);
+            (b0, -b0)
+        }
+        _ => {
+            let b0 = omega_c / (k + omega_c);
+            (b0, b0)
+        }
+    };
+
+    Ok(Coefficients {
+        a1,
+        a2: T::zero(),
+        b0,
+        b1,
+        b2: T::zero(),
+    })
+}
@@ -0,0 +1,315 @@
+//! Calculation of the biquad coefficients for second order IIR filters, based on the
+//! [Audio EQ Cookbook](https://www.w3.org/TR/audio-eq-cookbook/)
+
+use num_complex::Complex;
+
+use crate::{Errors, Hertz, Sample};
+
+/// Butterworth Q, used for maximally flat filter responses
+pub const Q_BUTTERWORTH: f32 = core::f32::consts::FRAC_1_SQRT_2;
+
+/// The available filter types, with the required parameters for each
+#[derive(Clone, Copy, Debug)]
+pub enum Type<T: Sample> {
+    SinglePoleLowPassApprox,
+    SinglePoleLowPass,
+    LowPass,
+    HighPass,
+    BandPass,
+    Notch,
+    AllPass,
+    LowShelf(T),
+    HighShelf(T),
+    PeakingEQ(T),
+}
+
+/// Coefficients of a second order IIR filter
+///
+/// Represents the difference equation
+/// `y[n] = b0*x[n] + b1*x[n-1] + b2*x[n-2] - a1*y[n-1] - a2*y[n-2]`
+/// with `a0` normalised to 1.
+#[derive(Clone, Copy, Debug)]
+pub struct Coefficients<T: Sample> {
+    // Denominator coefficients
+    pub a1: T,
+    pub a2: T,
+
+    // Numerator coefficients
+    pub b0: T,
+    pub b1: T,
+    pub b2: T,
+}
+
+impl<T: Sample> Coefficients<T> {
+    /// Creates coefficients for the given filter type, sampling frequency, cutoff frequency and
+    /// Q value
+    pub fn from_params(
+        filter: Type<T>,
+        fs: Hertz,
+        f0: Hertz,
+        q_value: T,
+    ) -> Result<Coefficients<T>, Errors> {
+        if f0.hz() * 2.0 > fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+
+        if q_value < T::zero() {
+            return Err(Errors::NegativeQ);
+        }
+
+        let omega = T::from(2.0).unwrap() * T::PI() * T::from(f0.hz()).unwrap()
+            / T::from(fs.hz()).unwrap();
+        let (sn, cs) = omega.sin_cos();
+        let alpha = sn / (T::from(2.0).unwrap() * q_value);
+
+        match filter {
+            Type::SinglePoleLowPassApprox => {
+                let omega = T::from(2.0).unwrap() * T::PI() * T::from(f0.hz()).unwrap()
+                    / T::from(fs.hz()).unwrap();
+                let alpha = omega / (omega + T::one());
+
+                Ok(Coefficients {
+                    a1: alpha - T::one(),
+                    a2: T::zero(),
+                    b0: alpha,
+                    b1: T::zero(),
+                    b2: T::zero(),
+                })
+            }
+            Type::SinglePoleLowPass => {
+                let t = T::from(2.0).unwrap() * T::PI() * T::from(f0.hz()).unwrap()
+                    / T::from(fs.hz()).unwrap();
+                let b0 = T::one() - (-t).exp();
+
+                Ok(Coefficients {
+                    a1: -(T::one() - b0),
+                    a2: T::zero(),
+                    b0,
+                    b1: T::zero(),
+                    b2: T::zero(),
+                })
+            }
+            Type::LowPass => {
+                let b0 = (T::one() - cs) / T::from(2.0).unwrap();
+                let b1 = T::one() - cs;
+                let b2 = (T::one() - cs) / T::from(2.0).unwrap();
+                let a0 = T::one() + alpha;
+                let a1 = T::from(-2.0).unwrap() * cs;
+                let a2 = T::one() - alpha;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+            Type::HighPass => {
+                let b0 = (T::one() + cs) / T::from(2.0).unwrap();
+                let b1 = -(T::one() + cs);
+                let b2 = (T::one() + cs) / T::from(2.0).unwrap();
+                let a0 = T::one() + alpha;
+                let a1 = T::from(-2.0).unwrap() * cs;
+                let a2 = T::one() - alpha;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+            Type::Notch => {
+                let b0 = T::one();
+                let b1 = T::from(-2.0).unwrap() * cs;
+                let b2 = T::one();
+                let a0 = T::one() + alpha;
+                let a1 = T::from(-2.0).unwrap() * cs;
+                let a2 = T::one() - alpha;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+            Type::BandPass => {
+                let b0 = alpha;
+                let b1 = T::zero();
+                let b2 = -alpha;
+                let a0 = T::one() + alpha;
+                let a1 = T::from(-2.0).unwrap() * cs;
+                let a2 = T::one() - alpha;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+            Type::AllPass => {
+                let b0 = T::one() - alpha;
+                let b1 = T::from(-2.0).unwrap() * cs;
+                let b2 = T::one() + alpha;
+                let a0 = T::one() + alpha;
+                let a1 = T::from(-2.0).unwrap() * cs;
+                let a2 = T::one() - alpha;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+            Type::LowShelf(db_gain) => {
+                let a = (db_gain / T::from(40.0).unwrap() * T::from(10.0).unwrap().ln()).exp();
+                let beta = (a.sqrt() / q_value).sqrt();
+
+                let b0 = a * ((a + T::one()) - (a - T::one()) * cs + beta * sn);
+                let b1 = T::from(2.0).unwrap() * a * ((a - T::one()) - (a + T::one()) * cs);
+                let b2 = a * ((a + T::one()) - (a - T::one()) * cs - beta * sn);
+                let a0 = (a + T::one()) + (a - T::one()) * cs + beta * sn;
+                let a1 = T::from(-2.0).unwrap() * ((a - T::one()) + (a + T::one()) * cs);
+                let a2 = (a + T::one()) + (a - T::one()) * cs - beta * sn;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+            Type::HighShelf(db_gain) => {
+                let a = (db_gain / T::from(40.0).unwrap() * T::from(10.0).unwrap().ln()).exp();
+                let beta = (a.sqrt() / q_value).sqrt();
+
+                let b0 = a * ((a + T::one()) + (a - T::one()) * cs + beta * sn);
+                let b1 = T::from(-2.0).unwrap() * a * ((a - T::one()) + (a + T::one()) * cs);
+                let b2 = a * ((a + T::one()) + (a - T::one()) * cs - beta * sn);
+                let a0 = (a + T::one()) - (a - T::one()) * cs + beta * sn;
+                let a1 = T::from(2.0).unwrap() * ((a - T::one()) - (a + T::one()) * cs);
+                let a2 = (a + T::one()) - (a - T::one()) * cs - beta * sn;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+            Type::PeakingEQ(db_gain) => {
+                let a = (db_gain / T::from(40.0).unwrap() * T::from(10.0).unwrap().ln()).exp();
+
+                let b0 = T::one() + alpha * a;
+                let b1 = T::from(-2.0).unwrap() * cs;
+                let b2 = T::one() - alpha * a;
+                let a0 = T::one() + alpha / a;
+                let a1 = T::from(-2.0).unwrap() * cs;
+                let a2 = T::one() - alpha / a;
+
+                Ok(Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                })
+            }
+        }
+    }
+
+    /// Designs coefficients from a continuous-time (analog) biquad prototype
+    /// `H(s) = (b0 + b1*s + b2*s^2) / (a0 + a1*s + a2*s^2)` via the bilinear transform
+    /// `s = K * (1 - z^-1) / (1 + z^-1)`, `K = 2 * fs`.
+    pub fn from_analog(fs: Hertz, b: [T; 3], a: [T; 3]) -> Result<Coefficients<T>, Errors> {
+        if fs.hz() <= 0.0 {
+            return Err(Errors::InvalidParameter);
+        }
+
+        let k = T::from(2.0).unwrap() * T::from(fs.hz()).unwrap();
+        let ksq = k * k;
+
+        let [b0, b1, b2] = b;
+        let [a0, a1, a2] = a;
+
+        let a0f = a2 * ksq + a1 * k + a0;
+
+        if a0f == T::zero() {
+            return Err(Errors::InvalidParameter);
+        }
+
+        Ok(Coefficients {
+            a1: T::from(2.0).unwrap() * (a0 - a2 * ksq) / a0f,
+            a2: (a2 * ksq - a1 * k + a0) / a0f,
+            b0: (b2 * ksq + b1 * k + b0) / a0f,
+            b1: T::from(2.0).unwrap() * (b0 - b2 * ksq) / a0f,
+            b2: (b2 * ksq - b1 * k + b0) / a0f,
+        })
+    }
+
+    /// Builds the `Coefficients` realising an ideal (unfiltered-derivative) discrete PID
+    /// regulator (gains `kp`, `ki`, `kd`, sample period `ts`) as a biquad. Pass `T::zero()` for
+    /// `ki`/`kd` for a pure P, PI or PD regulator. The denominator is a pure integrator (pole at
+    /// `z = 1`), so `a1 = -1` and `a2 = 0` always.
+    pub fn from_pid(kp: T, ki: T, kd: T, ts: T) -> Result<Coefficients<T>, Errors> {
+        if ts <= T::zero() {
+            return Err(Errors::InvalidParameter);
+        }
+
+        let two = T::from(2.0).unwrap();
+
+        let b0 = kp + ki * ts / two + kd / ts;
+        let b1 = -kp + ki * ts / two - two * kd / ts;
+        let b2 = kd / ts;
+
+        Ok(Coefficients {
+            a1: -T::one(),
+            a2: T::zero(),
+            b0,
+            b1,
+            b2,
+        })
+    }
+
+    /// Evaluates the transfer function `H(z) = (b0 + b1*z^-1 + b2*z^-2) / (1 + a1*z^-1 +
+    /// a2*z^-2)` at `z = e^{j*omega}`, `omega = 2*pi*f/fs`, giving the complex frequency
+    /// response at `f` for a filter running at sample rate `fs`.
+    pub fn frequency_response(&self, fs: Hertz, f: Hertz) -> Complex<T> {
+        let omega = T::from(2.0).unwrap() * T::PI() * T::from(f.hz()).unwrap()
+            / T::from(fs.hz()).unwrap();
+        let z_inv = Complex::new(omega.cos(), -omega.sin());
+        let z_inv2 = z_inv * z_inv;
+
+        let numerator = Complex::new(self.b0, T::zero())
+            + Complex::new(self.b1, T::zero()) * z_inv
+            + Complex::new(self.b2, T::zero()) * z_inv2;
+        let denominator = Complex::new(T::one(), T::zero())
+            + Complex::new(self.a1, T::zero()) * z_inv
+            + Complex::new(self.a2, T::zero()) * z_inv2;
+
+        numerator / denominator
+    }
+
+    /// The magnitude of the frequency response at `f`, in decibels (`20 * log10(|H|)`)
+    pub fn magnitude_db(&self, fs: Hertz, f: Hertz) -> T {
+        let h = self.frequency_response(fs, f);
+        let magnitude = (h.re * h.re + h.im * h.im).sqrt();
+        T::from(20.0).unwrap() * magnitude.log10()
+    }
+
+    /// The phase of the frequency response at `f`, in radians (`atan2(Im(H), Re(H))`)
+    pub fn phase_rad(&self, fs: Hertz, f: Hertz) -> T {
+        let h = self.frequency_response(fs, f);
+        h.im.atan2(h.re)
+    }
+}
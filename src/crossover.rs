@@ -0,0 +1,33 @@
+//! Linkwitz-Riley crossovers: matched lowpass/highpass pairs that recombine to unity magnitude,
+//! used for multi-band splitting and subwoofer bass management.
+
+use crate::{Cascade, Coefficients, Errors, Hertz, Sample, Type};
+
+/// A 4th order Linkwitz-Riley crossover: a matched lowpass/highpass pair, each built as two
+/// cascaded Butterworth second order sections at `Q = 1/sqrt(2)`, so each branch is -6 dB at the
+/// crossover frequency and the two branches recombine to unity magnitude.
+#[derive(Clone, Debug)]
+pub struct Crossover<T: Sample> {
+    low: Cascade<T, 2>,
+    high: Cascade<T, 2>,
+}
+
+impl<T: Sample> Crossover<T> {
+    /// Creates a Linkwitz-Riley crossover at sample rate `fs` and crossover frequency `f0`
+    pub fn new(fs: Hertz, f0: Hertz) -> Result<Self, Errors> {
+        let q = T::FRAC_1_SQRT_2();
+
+        let low_section = Coefficients::from_params(Type::LowPass, fs, f0, q)?;
+        let high_section = Coefficients::from_params(Type::HighPass, fs, f0, q)?;
+
+        Ok(Crossover {
+            low: Cascade::new([low_section, low_section]),
+            high: Cascade::new([high_section, high_section]),
+        })
+    }
+
+    /// Runs a single sample through both branches, returning `(low, high)`
+    pub fn split(&mut self, input: T) -> (T, T) {
+        (self.low.run(input), self.high.run(input))
+    }
+}
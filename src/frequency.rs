@@ -0,0 +1,83 @@
+//! Handling of frequency related information
+
+use crate::Errors;
+
+/// Possible conversions to Hertz, for use in specifying frequencies
+pub trait ToHertz {
+    /// Convert to Hertz
+    fn hz(self) -> Hertz;
+
+    /// Convert kHz to Hertz
+    fn khz(self) -> Hertz;
+
+    /// Convert MHz to Hertz
+    fn mhz(self) -> Hertz;
+
+    /// Convert a sample period in seconds to a sample rate in Hertz
+    fn dt(self) -> Hertz;
+}
+
+/// Representation of a frequency, in Hertz
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Hertz {
+    hz: f32,
+}
+
+impl Hertz {
+    /// Creates a new Hertz from a value in Hertz, erroring if it is negative
+    pub fn from_hz(hz: f32) -> Result<Self, Errors> {
+        if hz < 0.0 {
+            return Err(Errors::NegativeFrequency);
+        }
+        Ok(Hertz { hz })
+    }
+
+    /// Creates a new Hertz from a sample period in seconds, erroring if it is not positive
+    pub fn from_dt(dt: f32) -> Result<Self, Errors> {
+        if dt <= 0.0 {
+            return Err(Errors::NegativeFrequency);
+        }
+        Ok(Hertz { hz: 1.0 / dt })
+    }
+
+    /// The value in Hertz
+    pub fn hz(self) -> f32 {
+        self.hz
+    }
+}
+
+impl ToHertz for f32 {
+    fn hz(self) -> Hertz {
+        Hertz::from_hz(self).unwrap()
+    }
+
+    fn khz(self) -> Hertz {
+        Hertz::from_hz(self * 1_000.0).unwrap()
+    }
+
+    fn mhz(self) -> Hertz {
+        Hertz::from_hz(self * 1_000_000.0).unwrap()
+    }
+
+    fn dt(self) -> Hertz {
+        Hertz::from_dt(self).unwrap()
+    }
+}
+
+impl ToHertz for i32 {
+    fn hz(self) -> Hertz {
+        (self as f32).hz()
+    }
+
+    fn khz(self) -> Hertz {
+        (self as f32).khz()
+    }
+
+    fn mhz(self) -> Hertz {
+        (self as f32).mhz()
+    }
+
+    fn dt(self) -> Hertz {
+        (self as f32).dt()
+    }
+}
@@ -11,29 +11,27 @@
 //! # Examples
 //!
 //! ```
-//! fn main() {
-//!     use biquad::*;
+//! use biquad::*;
 //!
-//!     // Cutoff and sampling frequencies
-//!     let f0 = 10.hz();
-//!     let fs = 1.khz();
+//! // Cutoff and sampling frequencies
+//! let f0 = 10.hz();
+//! let fs = 1.khz();
 //!
-//!     // Create coefficients for the biquads
-//!     let coeffs = Coefficients::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH).unwrap();
+//! // Create coefficients for the biquads
+//! let coeffs = Coefficients::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH).unwrap();
 //!
-//!     // Create two different biquads
-//!     let mut biquad1 = DirectForm1::new(coeffs);
-//!     let mut biquad2 = DirectForm2Transposed::new(coeffs);
+//! // Create two different biquads
+//! let mut biquad1 = DirectForm1::new(coeffs);
+//! let mut biquad2 = DirectForm2Transposed::new(coeffs);
 //!
-//!     let input_vec = vec![0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
-//!     let mut output_vec1 = Vec::new();
-//!     let mut output_vec2 = Vec::new();
+//! let input_vec = vec![0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+//! let mut output_vec1 = Vec::new();
+//! let mut output_vec2 = Vec::new();
 //!
-//!     // Run for all the inputs
-//!     for elem in input_vec {
-//!         output_vec1.push(biquad1.run(elem));
-//!         output_vec2.push(biquad2.run(elem));
-//!     }
+//! // Run for all the inputs
+//! for elem in input_vec {
+//!     output_vec1.push(biquad1.run(elem));
+//!     output_vec2.push(biquad2.run(elem));
 //! }
 //! ```
 //!
@@ -47,22 +45,45 @@
 //!
 //! `Hertz::new(...)` will panic if the frequency is negative.
 //!
+//! # Sample type
+//!
+//! `Coefficients`, `Biquad`, `DirectForm1` and `DirectForm2Transposed` are generic over the
+//! sample type `T`, bound by the [`Sample`] trait. `f32` and `f64` are both supported out of the
+//! box; `f64` is useful for low-frequency filters where `f32` coefficient rounding becomes
+//! audible.
+//!
 
 #![no_std]
 
+pub mod cascade;
 pub mod coefficients;
+pub mod crossover;
 pub mod frequency;
+pub mod svf;
 
+pub use crate::cascade::*;
 pub use crate::coefficients::*;
+pub use crate::crossover::*;
 pub use crate::frequency::*;
+pub use crate::svf::*;
+
+use num_traits::{Float, FloatConst};
+
+/// The floating point types that `Coefficients` and the `Biquad` implementations can run on.
+///
+/// Implemented for `f32` and `f64`. In `no_std` environments this relies on `num-traits`'
+/// `libm` feature to provide the transcendental functions.
+pub trait Sample: Float + FloatConst {}
+
+impl<T: Float + FloatConst> Sample for T {}
 
 /// The required functions of a biquad implementation
-pub trait Biquad {
+pub trait Biquad<T: Sample> {
     /// A single iteration of a biquad, applying the filtering on the input
-    fn run(&mut self, input: f32) -> f32;
+    fn run(&mut self, input: T) -> T;
 
     /// Updating of coefficients
-    fn update_coefficients(&mut self, new_coefficients: Coefficients);
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>);
 }
 
 /// Possible errors
@@ -71,41 +92,42 @@ pub enum Errors {
     OutsideNyquist,
     NegativeQ,
     NegativeFrequency,
+    InvalidParameter,
 }
 
 /// Internal states and coefficients of the Direct Form 1 form
 #[derive(Copy, Clone, Debug)]
-pub struct DirectForm1 {
-    y1: f32,
-    y2: f32,
-    x1: f32,
-    x2: f32,
-    coeffs: Coefficients,
+pub struct DirectForm1<T: Sample> {
+    y1: T,
+    y2: T,
+    x1: T,
+    x2: T,
+    coeffs: Coefficients<T>,
 }
 
 /// Internal states and coefficients of the Direct Form 2 Transposed form
 #[derive(Copy, Clone, Debug)]
-pub struct DirectForm2Transposed {
-    pub s1: f32,
-    pub s2: f32,
-    coeffs: Coefficients,
+pub struct DirectForm2Transposed<T: Sample> {
+    pub s1: T,
+    pub s2: T,
+    coeffs: Coefficients<T>,
 }
 
-impl DirectForm1 {
+impl<T: Sample> DirectForm1<T> {
     /// Creates a Direct Form 1 biquad from a set of filter coefficients
-    pub fn new(coefficients: Coefficients) -> DirectForm1 {
+    pub fn new(coefficients: Coefficients<T>) -> Self {
         DirectForm1 {
-            y1: 0.0,
-            y2: 0.0,
-            x1: 0.0,
-            x2: 0.0,
+            y1: T::zero(),
+            y2: T::zero(),
+            x1: T::zero(),
+            x2: T::zero(),
             coeffs: coefficients,
         }
     }
 }
 
-impl Biquad for DirectForm1 {
-    fn run(&mut self, input: f32) -> f32 {
+impl<T: Sample> Biquad<T> for DirectForm1<T> {
+    fn run(&mut self, input: T) -> T {
         let out = self.coeffs.b0 * input + self.coeffs.b1 * self.x1 + self.coeffs.b2 * self.x2
             - self.coeffs.a1 * self.y1
             - self.coeffs.a2 * self.y2;
@@ -118,24 +140,24 @@ impl Biquad for DirectForm1 {
         out
     }
 
-    fn update_coefficients(&mut self, new_coefficients: Coefficients) {
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>) {
         self.coeffs = new_coefficients;
     }
 }
 
-impl DirectForm2Transposed {
+impl<T: Sample> DirectForm2Transposed<T> {
     /// Creates a Direct Form 2 Transposed biquad from a set of filter coefficients
-    pub fn new(coefficients: Coefficients) -> DirectForm2Transposed {
+    pub fn new(coefficients: Coefficients<T>) -> Self {
         DirectForm2Transposed {
-            s1: 0.0,
-            s2: 0.0,
+            s1: T::zero(),
+            s2: T::zero(),
             coeffs: coefficients,
         }
     }
 }
 
-impl Biquad for DirectForm2Transposed {
-    fn run(&mut self, input: f32) -> f32 {
+impl<T: Sample> Biquad<T> for DirectForm2Transposed<T> {
+    fn run(&mut self, input: T) -> T {
         let out = self.s1 + self.coeffs.b0 * input;
         self.s1 = self.s2 + self.coeffs.b1 * input - self.coeffs.a1 * out;
         self.s2 = self.coeffs.b2 * input - self.coeffs.a2 * out;
@@ -143,7 +165,7 @@ impl Biquad for DirectForm2Transposed {
         out
     }
 
-    fn update_coefficients(&mut self, new_coefficients: Coefficients) {
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>) {
         self.coeffs = new_coefficients;
     }
 }
@@ -256,4 +278,171 @@ mod tests {
             output_vec2.push(biquad2.run(elem));
         }
     }
+
+    #[test]
+    fn test_cascade_butterworth_minus_3db_at_cutoff() {
+        let fs = 1.khz();
+        let f0 = 100.hz();
+
+        let db2 = Cascade::<f32, 1>::butterworth(Type::LowPass, fs, f0, 2)
+            .unwrap()
+            .magnitude_db(fs, f0);
+        let db3 = Cascade::<f32, 2>::butterworth(Type::LowPass, fs, f0, 3)
+            .unwrap()
+            .magnitude_db(fs, f0);
+        let db4 = Cascade::<f32, 2>::butterworth(Type::LowPass, fs, f0, 4)
+            .unwrap()
+            .magnitude_db(fs, f0);
+        let db5 = Cascade::<f32, 3>::butterworth(Type::LowPass, fs, f0, 5)
+            .unwrap()
+            .magnitude_db(fs, f0);
+
+        for db in [db2, db3, db4, db5] {
+            assert!((db - (-3.0103)).abs() < 0.05, "got {} dB at cutoff", db);
+        }
+    }
+
+    #[test]
+    fn test_odd_order_cascade_impulse_response_decays() {
+        // Regression test: an odd-order cascade's first order section used to carry a spurious
+        // pole/zero pair at z = -1 that never fully cancelled in floating point, leaving a
+        // non-decaying Nyquist-frequency limit cycle in the impulse response.
+        let fs = 1.khz();
+        let f0 = 100.hz();
+
+        for filter_type in [Type::LowPass, Type::HighPass] {
+            let mut cascade = Cascade::<f32, 2>::butterworth(filter_type, fs, f0, 3).unwrap();
+
+            let mut out = cascade.run(1.0);
+            for _ in 0..199 {
+                out = cascade.run(0.0);
+            }
+            assert!(
+                out.abs() < 1e-6,
+                "impulse response failed to decay ({:?}): {}",
+                filter_type,
+                out
+            );
+        }
+    }
+
+    #[test]
+    fn test_cascade_set_coefficients_keeps_run_and_magnitude_in_sync() {
+        let fs = 1.khz();
+
+        let mut cascade = Cascade::<f32, 1>::butterworth(Type::LowPass, fs, 100.hz(), 2).unwrap();
+        let retuned = Cascade::<f32, 1>::butterworth(Type::LowPass, fs, 200.hz(), 2).unwrap();
+
+        cascade.set_coefficients([Coefficients::from_params(
+            Type::LowPass,
+            fs,
+            200.hz(),
+            Q_BUTTERWORTH,
+        )
+        .unwrap()]);
+
+        assert_eq!(
+            cascade.magnitude_db(fs, 200.hz()),
+            retuned.magnitude_db(fs, 200.hz())
+        );
+        assert_eq!(cascade.run(1.0), retuned.clone().run(1.0));
+    }
+
+    #[test]
+    fn test_frequency_response_matches_magnitude_and_phase() {
+        let f0 = 10.hz();
+        let fs = 1.khz();
+
+        let coeffs = Coefficients::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH).unwrap();
+
+        // Gain is unity and phase is zero at DC
+        assert!((coeffs.magnitude_db(fs, 0.hz()) - 0.0).abs() < 1e-3);
+        assert!((coeffs.phase_rad(fs, 0.hz()) - 0.0).abs() < 1e-3);
+
+        // Cutoff is the Butterworth -3.01 dB point
+        assert!((coeffs.magnitude_db(fs, f0) - (-3.0103)).abs() < 0.01);
+
+        let h = coeffs.frequency_response(fs, f0);
+        let magnitude_db = 20.0 * (h.re * h.re + h.im * h.im).sqrt().log10();
+        assert!((magnitude_db - coeffs.magnitude_db(fs, f0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_svf_dc_response() {
+        let fs = 1.khz();
+        let fc = 100.hz();
+
+        let mut svf = StateVariableFilter::new(fs, fc, 1.0).unwrap();
+
+        let mut outputs = svf.run(1.0);
+        for _ in 0..1000 {
+            outputs = svf.run(1.0);
+        }
+
+        // At DC, the lowpass output converges to the input and the highpass output to zero
+        assert!((outputs.lowpass - 1.0).abs() < 1e-3);
+        assert!(outputs.highpass.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_from_analog_unity_gain_lowpass_at_dc() {
+        let fs = 1.khz();
+        let omega_c = 2.0 * core::f32::consts::PI * 50.0;
+
+        // H(s) = omega_c / (s + omega_c), a one-pole analog lowpass prototype
+        let coeffs =
+            Coefficients::from_analog(fs, [omega_c, 0.0, 0.0], [omega_c, 1.0, 0.0]).unwrap();
+
+        assert!((coeffs.magnitude_db(fs, 0.hz()) - 0.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_from_analog_fails_on_degenerate_prototype() {
+        let fs = 1.khz();
+
+        let err = Coefficients::<f32>::from_analog(fs, [1.0, 0.0, 0.0], [0.0, 0.0, 0.0])
+            .unwrap_err();
+        assert_eq!(err, Errors::InvalidParameter);
+    }
+
+    #[test]
+    fn test_from_pid_drives_error_to_zero() {
+        let ts = 1.0e-3;
+        let coeffs = Coefficients::from_pid(1.0, 50.0, 0.0, ts).unwrap();
+
+        let mut pid = DirectForm1::new(coeffs);
+
+        // A constant setpoint error should make the (integrating) controller output grow
+        let first = pid.run(1.0);
+        let later = {
+            let mut out = first;
+            for _ in 0..10 {
+                out = pid.run(1.0);
+            }
+            out
+        };
+        assert!(later > first);
+    }
+
+    #[test]
+    fn test_from_pid_fails_on_non_positive_ts() {
+        let err = Coefficients::from_pid(1.0, 1.0, 0.0, 0.0).unwrap_err();
+        assert_eq!(err, Errors::InvalidParameter);
+    }
+
+    #[test]
+    fn test_crossover_branches_recombine_to_unity_at_dc() {
+        let fs = 48.khz();
+        let f0 = 1.khz();
+        let mut crossover = Crossover::new(fs, f0).unwrap();
+
+        let mut low = 0.0;
+        let mut high = 0.0;
+        for _ in 0..1000 {
+            (low, high) = crossover.split(1.0);
+        }
+
+        // At DC, the lowpass branch passes the input through and the highpass branch is silent
+        assert!((low + high - 1.0).abs() < 1e-3);
+    }
 }
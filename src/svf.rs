@@ -0,0 +1,89 @@
+//! A topology-preserving-transform (TPT) state variable filter, giving simultaneous lowpass,
+//! highpass, bandpass and notch outputs from a single stage.
+
+use crate::{Errors, Hertz, Sample};
+
+/// The four simultaneous outputs of a `StateVariableFilter` iteration
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SvfOutputs<T: Sample> {
+    pub lowpass: T,
+    pub highpass: T,
+    pub bandpass: T,
+    pub notch: T,
+}
+
+/// A Zavalishin trapezoidal (TPT) state variable filter
+#[derive(Copy, Clone, Debug)]
+pub struct StateVariableFilter<T: Sample> {
+    g: T,
+    k: T,
+    a1: T,
+    a2: T,
+    a3: T,
+    ic1eq: T,
+    ic2eq: T,
+}
+
+impl<T: Sample> StateVariableFilter<T> {
+    /// Creates a state variable filter for cutoff `fc`, sample rate `fs` and resonance `q`
+    pub fn new(fs: Hertz, fc: Hertz, q: T) -> Result<Self, Errors> {
+        if fc.hz() * 2.0 > fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+
+        if q <= T::zero() {
+            return Err(Errors::NegativeQ);
+        }
+
+        let mut svf = StateVariableFilter {
+            g: T::zero(),
+            k: T::zero(),
+            a1: T::zero(),
+            a2: T::zero(),
+            a3: T::zero(),
+            ic1eq: T::zero(),
+            ic2eq: T::zero(),
+        };
+        svf.set_params(fs, fc, q);
+
+        Ok(svf)
+    }
+
+    /// Retunes the filter, e.g. in response to a modulated cutoff or resonance. Cheap enough to
+    /// call every sample.
+    pub fn set_params(&mut self, fs: Hertz, fc: Hertz, q: T) {
+        let g = (T::PI() * T::from(fc.hz()).unwrap() / T::from(fs.hz()).unwrap()).tan();
+        let k = T::one() / q;
+        let a1 = T::one() / (T::one() + g * (g + k));
+        let a2 = g * a1;
+        let a3 = g * a2;
+
+        self.g = g;
+        self.k = k;
+        self.a1 = a1;
+        self.a2 = a2;
+        self.a3 = a3;
+    }
+
+    /// A single iteration of the filter, returning all four simultaneous outputs
+    pub fn run(&mut self, input: T) -> SvfOutputs<T> {
+        let v3 = input - self.ic2eq;
+        let v1 = self.a1 * self.ic1eq + self.a2 * v3;
+        let v2 = self.ic2eq + self.a2 * self.ic1eq + self.a3 * v3;
+
+        self.ic1eq = T::from(2.0).unwrap() * v1 - self.ic1eq;
+        self.ic2eq = T::from(2.0).unwrap() * v2 - self.ic2eq;
+
+        let lowpass = v2;
+        let bandpass = v1;
+        let highpass = input - self.k * v1 - v2;
+        let notch = highpass + lowpass;
+
+        SvfOutputs {
+            lowpass,
+            highpass,
+            bandpass,
+            notch,
+        }
+    }
+}